@@ -0,0 +1,107 @@
+use crate::browser;
+use anyhow::{anyhow, Result};
+use futures::channel::oneshot::channel;
+use std::rc::Rc;
+use std::sync::Mutex;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AudioBuffer, AudioContext};
+
+#[derive(Clone)]
+pub struct Sound {
+    buffer: AudioBuffer,
+}
+
+#[derive(Clone, Copy)]
+pub enum Looping {
+    No,
+    Yes,
+}
+
+pub struct Audio {
+    context: AudioContext,
+}
+
+impl Audio {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            context: AudioContext::new()
+                .map_err(|err| anyhow!("Could not create AudioContext {:#?}", err))?,
+        })
+    }
+
+    pub async fn load_sound(&self, source: &str) -> Result<Sound> {
+        let array_buffer = fetch_array_buffer(source).await?;
+        let buffer = decode_audio_data(&self.context, array_buffer).await?;
+        Ok(Sound { buffer })
+    }
+
+    pub fn play_sound(&self, sound: &Sound, looping: Looping) -> Result<()> {
+        let track_source = self
+            .context
+            .create_buffer_source()
+            .map_err(|err| anyhow!("Could not create buffer source {:#?}", err))?;
+
+        track_source.set_buffer(Some(&sound.buffer));
+        track_source
+            .connect_with_audio_node(&self.context.destination())
+            .map_err(|err| anyhow!("Could not connect track source to destination {:#?}", err))?;
+
+        if let Looping::Yes = looping {
+            track_source.set_loop(true);
+        }
+
+        track_source
+            .start()
+            .map_err(|err| anyhow!("Could not start source {:#?}", err))
+    }
+}
+
+async fn fetch_array_buffer(source: &str) -> Result<js_sys::ArrayBuffer> {
+    let response = browser::fetch_with_str(source).await?;
+    let array_buffer = JsFuture::from(
+        response
+            .array_buffer()
+            .map_err(|err| anyhow!("Error reading array buffer from {} {:#?}", source, err))?,
+    )
+    .await
+    .map_err(|err| anyhow!("Error awaiting array buffer from {} {:#?}", source, err))?;
+
+    array_buffer
+        .dyn_into()
+        .map_err(|err| anyhow!("Error converting {} to an ArrayBuffer {:#?}", source, err))
+}
+
+async fn decode_audio_data(
+    ctx: &AudioContext,
+    mut array_buffer: js_sys::ArrayBuffer,
+) -> Result<AudioBuffer> {
+    let (complete_tx, complete_rx) = channel::<Result<AudioBuffer>>();
+    // 排他制御を行うために Mutex が必要
+    // 2 つの closure で success_tx の所有権を共有するために Rc が必要
+    let success_tx = Rc::new(Mutex::new(Some(complete_tx)));
+    let error_tx = Rc::clone(&success_tx);
+
+    let success_callback: Closure<dyn FnMut(JsValue)> =
+        browser::closure_once(move |buffer: JsValue| {
+            if let Some(success_tx) = success_tx.lock().ok().and_then(|mut opt| opt.take()) {
+                success_tx.send(Ok(buffer.unchecked_into()));
+            }
+        });
+
+    let error_callback: Closure<dyn FnMut(JsValue)> = browser::closure_once(move |err| {
+        if let Some(error_tx) = error_tx.lock().ok().and_then(|mut opt| opt.take()) {
+            error_tx.send(Err(anyhow!("Error decoding audio data {:#?}", err)));
+        }
+    });
+
+    ctx.decode_audio_data_with_success_callback_and_error_callback(
+        &mut array_buffer,
+        success_callback.as_ref().unchecked_ref(),
+        error_callback.as_ref().unchecked_ref(),
+    )
+    .map_err(|err| anyhow!("Could not decode audio data {:#?}", err))?;
+
+    complete_rx.await?
+}
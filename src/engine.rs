@@ -12,6 +12,9 @@ use std::{cell::RefCell, collections::HashMap};
 use wasm_bindgen::prelude::*;
 use web_sys::{CanvasRenderingContext2d, HtmlImageElement};
 
+/// マウスのボタン番号 (`MouseEvent.button`) をキーとして押下状態を管理する
+type MouseButton = i16;
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Point {
     pub x: i16,
@@ -71,8 +74,11 @@ pub async fn load_image(source: &str) -> Result<HtmlImageElement> {
 #[async_trait(?Send)]
 pub trait Game {
     async fn initialize(&self) -> Result<Box<dyn Game>>;
-    fn update(&mut self, keystate: &KeyState);
-    fn draw(&self, renderer: &Renderer);
+    fn update(&mut self, keystate: &KeyState, mousestate: &MouseState) -> Result<()>;
+    fn draw(&self, renderer: &Renderer) -> Result<()>;
+
+    /// update/draw がエラーを返した際に GameLoop から呼び出される。デフォルトは何もしない
+    fn error_occurred(&mut self, _err: anyhow::Error) {}
 }
 const FRAME_SIZE: f32 = 1.0 / 60.0 * 1000.0;
 pub struct GameLoop {
@@ -85,6 +91,7 @@ type SharedLoopClosure = Rc<RefCell<Option<browser::LoopClosure>>>;
 impl GameLoop {
     pub async fn start(game: impl Game + 'static) -> Result<()> {
         let mut keyevent_receiver = prepare_input()?;
+        let mut mouseevent_receiver = prepare_mouse_input()?;
         let mut game = game.initialize().await?;
         let mut game_loop = Self {
             last_frame: browser::now()?,
@@ -113,15 +120,22 @@ impl GameLoop {
         let g = Rc::clone(&f);
 
         let mut keystate = KeyState::new();
+        let mut mousestate = MouseState::new();
         *g.borrow_mut() = Some(browser::create_raf_closure(move |perf: f64| {
             process_input(&mut keystate, &mut keyevent_receiver);
+            process_mouse_input(&mut mousestate, &mut mouseevent_receiver);
             game_loop.accumulated_delta += (perf - game_loop.last_frame) as f32;
             while game_loop.accumulated_delta > FRAME_SIZE {
-                game.update(&keystate);
+                if let Err(err) = game.update(&keystate, &mousestate) {
+                    game.error_occurred(err);
+                }
                 game_loop.accumulated_delta -= FRAME_SIZE;
             }
+            mousestate.reset_wheel_delta();
             game_loop.last_frame = perf;
-            game.draw(&renderer);
+            if let Err(err) = game.draw(&renderer) {
+                game.error_occurred(err);
+            }
             browser::request_animation_frame(f.borrow().as_ref().unwrap());
         }));
 
@@ -150,7 +164,12 @@ impl Renderer {
 
     /// * `frame` - sprite から切り出す矩形
     /// * `destination` - canvas 上に表示する位置
-    pub fn draw_image(&self, image: &HtmlImageElement, frame: &Rect, destination: &Rect) {
+    pub fn draw_image(
+        &self,
+        image: &HtmlImageElement,
+        frame: &Rect,
+        destination: &Rect,
+    ) -> Result<()> {
         self.draw_rect(destination);
         self.context
             .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
@@ -164,7 +183,7 @@ impl Renderer {
                 destination.width.into(),
                 destination.height.into(),
             )
-            .expect("Drawing is throwing exceptions! Unrecoverable error.");
+            .map_err(|err| anyhow!("Error drawing image {:#?}", err))
     }
 
     pub fn draw_rect(&self, rect: &Rect) {
@@ -178,10 +197,119 @@ impl Renderer {
         self.context.stroke();
     }
 
-    pub fn draw_entire_image(&self, image: &HtmlImageElement, position: &Point) {
+    pub fn draw_entire_image(&self, image: &HtmlImageElement, position: &Point) -> Result<()> {
         self.context
             .draw_image_with_html_image_element(image, position.x.into(), position.y.into())
-            .expect("Drawing is throwing exceptions! Unrecoverable error.")
+            .map_err(|err| anyhow!("Error drawing image {:#?}", err))
+    }
+
+    /// * `position` - テキストのベースライン位置
+    pub fn draw_text(
+        &self,
+        text: &str,
+        position: &Point,
+        font: &str,
+        mode: &TextMode,
+    ) -> Result<()> {
+        self.context.set_font(font);
+        self.context.set_text_align(mode.align().as_str());
+
+        match mode {
+            TextMode::Fill { color, .. } => {
+                self.context.set_fill_style(&JsValue::from_str(color));
+                self.fill_text(text, position)?;
+            }
+            TextMode::Stroke { color, .. } => {
+                self.context.set_stroke_style(&JsValue::from_str(color));
+                self.stroke_text(text, position)?;
+            }
+            TextMode::Shaded {
+                foreground,
+                background,
+                align,
+            } => {
+                let metrics = self
+                    .context
+                    .measure_text(text)
+                    .map_err(|err| anyhow!("Error measuring text {:#?}", err))?;
+                let width = metrics.width() as i16;
+                let ascent = metrics.actual_bounding_box_ascent() as i16;
+                let descent = metrics.actual_bounding_box_descent() as i16;
+                // 背景矩形は set_text_align で実際に描かれる位置に合わせる必要がある
+                let left = match align {
+                    TextAlign::Left => position.x,
+                    TextAlign::Center => position.x - width / 2,
+                    TextAlign::Right => position.x - width,
+                };
+
+                self.context.set_fill_style(&JsValue::from_str(background));
+                self.context.fill_rect(
+                    left.into(),
+                    (position.y - ascent).into(),
+                    width.into(),
+                    (ascent + descent).into(),
+                );
+
+                self.context.set_fill_style(&JsValue::from_str(foreground));
+                self.fill_text(text, position)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_text(&self, text: &str, position: &Point) -> Result<()> {
+        self.context
+            .fill_text(text, position.x.into(), position.y.into())
+            .map_err(|err| anyhow!("Error filling text {:#?}", err))
+    }
+
+    fn stroke_text(&self, text: &str, position: &Point) -> Result<()> {
+        self.context
+            .stroke_text(text, position.x.into(), position.y.into())
+            .map_err(|err| anyhow!("Error stroking text {:#?}", err))
+    }
+}
+
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl TextAlign {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TextAlign::Left => "left",
+            TextAlign::Center => "center",
+            TextAlign::Right => "right",
+        }
+    }
+}
+
+pub enum TextMode {
+    Fill {
+        color: String,
+        align: TextAlign,
+    },
+    Stroke {
+        color: String,
+        align: TextAlign,
+    },
+    Shaded {
+        foreground: String,
+        background: String,
+        align: TextAlign,
+    },
+}
+
+impl TextMode {
+    fn align(&self) -> &TextAlign {
+        match self {
+            TextMode::Fill { align, .. } => align,
+            TextMode::Stroke { align, .. } => align,
+            TextMode::Shaded { align, .. } => align,
+        }
     }
 }
 
@@ -302,6 +430,144 @@ fn process_input(state: &mut KeyState, keyevent_receiver: &mut UnboundedReceiver
     }
 }
 
+enum MouseEvent {
+    MouseMove(web_sys::MouseEvent),
+    MouseDown(web_sys::MouseEvent),
+    MouseUp(web_sys::MouseEvent),
+    Wheel(web_sys::WheelEvent),
+}
+
+fn prepare_mouse_input() -> Result<UnboundedReceiver<MouseEvent>> {
+    let (mouse_sender, mouse_receiver) = unbounded();
+    let mouse_sender = Rc::new(RefCell::new(mouse_sender));
+
+    let move_sender = Rc::clone(&mouse_sender);
+    let onmousemove = browser::closure_wrap(Box::new(move |event: web_sys::MouseEvent| {
+        move_sender
+            .borrow_mut()
+            .start_send(MouseEvent::MouseMove(event));
+    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+
+    let down_sender = Rc::clone(&mouse_sender);
+    let onmousedown = browser::closure_wrap(Box::new(move |event: web_sys::MouseEvent| {
+        down_sender
+            .borrow_mut()
+            .start_send(MouseEvent::MouseDown(event));
+    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+
+    let up_sender = Rc::clone(&mouse_sender);
+    let onmouseup = browser::closure_wrap(Box::new(move |event: web_sys::MouseEvent| {
+        up_sender
+            .borrow_mut()
+            .start_send(MouseEvent::MouseUp(event));
+    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+
+    let wheel_sender = Rc::clone(&mouse_sender);
+    let onwheel = browser::closure_wrap(Box::new(move |event: web_sys::WheelEvent| {
+        wheel_sender
+            .borrow_mut()
+            .start_send(MouseEvent::Wheel(event));
+    }) as Box<dyn FnMut(web_sys::WheelEvent)>);
+
+    let canvas = browser::context()?
+        .canvas()
+        .ok_or_else(|| anyhow!("prepare_mouse_input: canvas not found on context"))?;
+
+    canvas.set_onmousemove(Some(onmousemove.as_ref().unchecked_ref()));
+    canvas.set_onmousedown(Some(onmousedown.as_ref().unchecked_ref()));
+    canvas.set_onmouseup(Some(onmouseup.as_ref().unchecked_ref()));
+    canvas.set_onwheel(Some(onwheel.as_ref().unchecked_ref()));
+
+    onmousemove.forget();
+    onmousedown.forget();
+    onmouseup.forget();
+    onwheel.forget();
+
+    Ok(mouse_receiver)
+}
+
+/// ブラウザの mousemove イベントはビューポート基準のクライアント座標 (clientX/clientY) で届くため、canvas の
+/// bounding rect を引いて canvas 座標系に直す
+fn point_in_canvas(event: &web_sys::MouseEvent) -> Result<Point> {
+    let rect = browser::context()?
+        .canvas()
+        .ok_or_else(|| anyhow!("point_in_canvas: canvas not found on context"))?
+        .get_bounding_client_rect();
+
+    Ok(Point {
+        x: (event.client_x() as f64 - rect.left()) as i16,
+        y: (event.client_y() as f64 - rect.top()) as i16,
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct MouseState {
+    position: Point,
+    pressed_buttons: HashMap<MouseButton, web_sys::MouseEvent>,
+    wheel_delta: f64,
+}
+
+impl MouseState {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    pub fn is_button_down(&self, button: MouseButton) -> bool {
+        self.pressed_buttons.contains_key(&button)
+    }
+
+    /// 直近フレームで蓄積したホイールの移動量。毎フレーム末尾でリセットされる
+    pub fn wheel_delta(&self) -> f64 {
+        self.wheel_delta
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn set_button_down(&mut self, button: MouseButton, event: web_sys::MouseEvent) {
+        self.pressed_buttons.insert(button, event);
+    }
+
+    fn set_button_up(&mut self, button: MouseButton) {
+        self.pressed_buttons.remove(&button);
+    }
+
+    fn add_wheel_delta(&mut self, delta: f64) {
+        self.wheel_delta += delta;
+    }
+
+    fn reset_wheel_delta(&mut self) {
+        self.wheel_delta = 0.0;
+    }
+}
+
+fn process_mouse_input(
+    state: &mut MouseState,
+    mouseevent_receiver: &mut UnboundedReceiver<MouseEvent>,
+) {
+    loop {
+        match mouseevent_receiver.try_next() {
+            Ok(None) => break,
+            Err(_err) => break,
+            Ok(Some(evt)) => match evt {
+                MouseEvent::MouseMove(event) => {
+                    if let Ok(point) = point_in_canvas(&event) {
+                        state.set_position(point);
+                    }
+                }
+                MouseEvent::MouseDown(event) => state.set_button_down(event.button(), event),
+                MouseEvent::MouseUp(event) => state.set_button_up(event.button()),
+                MouseEvent::Wheel(event) => state.add_wheel_delta(event.delta_y()),
+            },
+        }
+    }
+}
+
 pub struct Image {
     element: HtmlImageElement,
     bounding_box: Rect,
@@ -320,14 +586,14 @@ impl Image {
         }
     }
 
-    pub fn draw(&self, renderer: &Renderer) {
+    pub fn draw(&self, renderer: &Renderer) -> Result<()> {
         // TODO: バウンディングボックス表示用
         renderer.draw_rect(&Rect {
             position: self.bounding_box.position,
             width: self.element.width() as i16,
             height: self.element.height() as i16,
         });
-        renderer.draw_entire_image(&self.element, &self.bounding_box.position);
+        renderer.draw_entire_image(&self.element, &self.bounding_box.position)
     }
 
     pub fn bounding_box(&self) -> &Rect {
@@ -361,7 +627,7 @@ impl SpriteSheet {
         self.sheet.frames.get(name)
     }
 
-    pub fn draw(&self, renderer: &Renderer, source: &Rect, destination: &Rect) {
-        renderer.draw_image(&self.image, source, destination);
+    pub fn draw(&self, renderer: &Renderer, source: &Rect, destination: &Rect) -> Result<()> {
+        renderer.draw_image(&self.image, source, destination)
     }
 }
@@ -0,0 +1,164 @@
+use crate::engine::{Rect, Renderer};
+
+/// von Neumann 近傍 (上下左右) のオフセット
+const NEIGHBOR_OFFSETS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// 矩形のセルが `cols` x `rows` に並んだフィールド。ブロック崩しのブロック配置や
+/// セルオートマトンの盤面のように、セルごとに状態を持つゲームで使う
+pub struct Grid<T> {
+    cells: Vec<T>,
+    cols: usize,
+    rows: usize,
+    cell_size: i16,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(cols: usize, rows: usize, cell_size: i16, default: T) -> Self {
+        Self {
+            cells: vec![default; cols * rows],
+            cols,
+            rows,
+            cell_size,
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn get(&self, col: usize, row: usize) -> Option<&T> {
+        self.index(col, row).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, col: usize, row: usize) -> Option<&mut T> {
+        let index = self.index(col, row)?;
+        Some(&mut self.cells[index])
+    }
+
+    pub fn set(&mut self, col: usize, row: usize, value: T) {
+        if let Some(i) = self.index(col, row) {
+            self.cells[i] = value;
+        }
+    }
+
+    pub fn cell_rect(&self, col: usize, row: usize) -> Rect {
+        Rect::new_from_x_y(
+            col as i16 * self.cell_size,
+            row as i16 * self.cell_size,
+            self.cell_size,
+            self.cell_size,
+        )
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T, Rect)> {
+        self.cells.iter().enumerate().map(move |(i, cell)| {
+            let col = i % self.cols;
+            let row = i / self.cols;
+            (col, row, cell, self.cell_rect(col, row))
+        })
+    }
+
+    pub fn draw(&self, renderer: &Renderer, draw_cell: impl Fn(&T, &Rect, &Renderer)) {
+        for (_, _, cell, rect) in self.iter() {
+            draw_cell(cell, &rect, renderer);
+        }
+    }
+
+    /// 各セルの次の状態を、現在の状態と近傍セルから `rule` で計算し、
+    /// 二重バッファに書き出してから入れ替える
+    pub fn step(&mut self, rule: impl Fn(&T, &[&T]) -> T) {
+        let mut next = Vec::with_capacity(self.cells.len());
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let neighbors = self.neighbors(col, row);
+                let current = self
+                    .get(col, row)
+                    .expect("col/row is always in bounds here");
+                next.push(rule(current, &neighbors));
+            }
+        }
+        self.cells = next;
+    }
+
+    /// 境界上のセルは盤外を跨ぐ近傍を持たないよう、範囲外のインデックスを除外する
+    fn neighbors(&self, col: usize, row: usize) -> Vec<&T> {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .filter_map(|(dx, dy)| {
+                let nc = col as i32 + dx;
+                let nr = row as i32 + dy;
+                if nc < 0 || nr < 0 {
+                    return None;
+                }
+                self.get(nc as usize, nr as usize)
+            })
+            .collect()
+    }
+
+    fn index(&self, col: usize, row: usize) -> Option<usize> {
+        if col < self.cols && row < self.rows {
+            Some(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    #[test]
+    fn a_single_cell_grid_has_no_neighbors() {
+        let grid = Grid::new(1, 1, 10, 0);
+
+        assert_eq!(grid.neighbors(0, 0).len(), 0);
+    }
+
+    #[test]
+    fn top_left_corner_only_has_in_bounds_neighbors() {
+        let grid = Grid::new(3, 3, 10, 0);
+
+        // (0, 0) から見えるのは右 (1, 0) と下 (0, 1) のみ
+        assert_eq!(grid.neighbors(0, 0).len(), 2);
+    }
+
+    #[test]
+    fn bottom_right_corner_only_has_in_bounds_neighbors() {
+        let grid = Grid::new(3, 3, 10, 0);
+
+        // (2, 2) から見えるのは左 (1, 2) と上 (2, 1) のみ
+        assert_eq!(grid.neighbors(2, 2).len(), 2);
+    }
+
+    #[test]
+    fn an_interior_cell_has_all_four_neighbors() {
+        let grid = Grid::new(3, 3, 10, 0);
+
+        assert_eq!(grid.neighbors(1, 1).len(), 4);
+    }
+
+    #[test]
+    fn step_never_panics_at_the_edges_of_a_single_cell_grid() {
+        let mut grid = Grid::new(1, 1, 10, 0);
+
+        grid.step(|current, neighbors| current + neighbors.len() as i32);
+
+        assert_eq!(*grid.get(0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn step_applies_the_rule_using_only_in_bounds_neighbors() {
+        let mut grid = Grid::new(3, 3, 10, 1);
+
+        grid.step(|_current, neighbors| neighbors.len() as i32);
+
+        assert_eq!(*grid.get(0, 0).unwrap(), 2);
+        assert_eq!(*grid.get(2, 2).unwrap(), 2);
+        assert_eq!(*grid.get(1, 1).unwrap(), 4);
+    }
+}
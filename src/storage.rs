@@ -0,0 +1,40 @@
+use crate::browser;
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub fn get_item(key: &str) -> Option<String> {
+    local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(key).ok().flatten())
+}
+
+pub fn set_item(key: &str, value: &str) -> Result<()> {
+    local_storage()?
+        .ok_or_else(|| anyhow!("No localStorage available on this window"))?
+        .set_item(key, value)
+        .map_err(|err| anyhow!("Could not set item {} in localStorage {:#?}", key, err))
+}
+
+/// `value` を JSON にシリアライズして localStorage に保存する
+pub fn save<T: Serialize>(key: &str, value: &T) -> Result<()> {
+    let json = serde_json::to_string(value)?;
+    set_item(key, &json)
+}
+
+/// localStorage から JSON を読み出してデシリアライズする。キーが存在しない場合は `None`
+pub fn load<T: DeserializeOwned>(key: &str) -> Result<Option<T>> {
+    get_item(key)
+        .map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|err| anyhow!("Could not parse {} from localStorage {:#?}", key, err))
+        })
+        .transpose()
+}
+
+fn local_storage() -> Result<Option<web_sys::Storage>> {
+    browser::window()?
+        .local_storage()
+        .map_err(|err| anyhow!("Could not access localStorage {:#?}", err))
+}
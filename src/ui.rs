@@ -0,0 +1,122 @@
+use crate::browser;
+use crate::engine::{Point, Rect, Renderer};
+use anyhow::{anyhow, Result};
+
+/// 子要素を水平方向のどこに揃えるか
+pub enum HAttach {
+    Left,
+    Center,
+    Right,
+}
+
+/// 子要素を垂直方向のどこに揃えるか
+pub enum VAttach {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// 論理的なデザイン解像度を実際の canvas サイズへどう写像するか
+pub enum ScaleMode {
+    /// canvas サイズに合わせて一様にスケーリングする
+    Scaled,
+    /// 常に指定した倍率を使う
+    Unscaled(f32),
+}
+
+pub trait UiElement {
+    fn draw(&self, renderer: &Renderer, rect: &Rect);
+}
+
+struct Anchored {
+    element: Box<dyn UiElement>,
+    h_attach: HAttach,
+    v_attach: VAttach,
+    offset: Point,
+    size: (i16, i16),
+}
+
+/// デザイン解像度上でアンカー配置された子要素を、実際の canvas サイズに
+/// スケーリングしてから描画するコンテナ
+pub struct Container {
+    design_resolution: (i16, i16),
+    scale_mode: ScaleMode,
+    children: Vec<Anchored>,
+}
+
+impl Container {
+    pub fn new(design_resolution: (i16, i16), scale_mode: ScaleMode) -> Self {
+        Self {
+            design_resolution,
+            scale_mode,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn add_child(
+        &mut self,
+        element: Box<dyn UiElement>,
+        h_attach: HAttach,
+        v_attach: VAttach,
+        offset: Point,
+        size: (i16, i16),
+    ) {
+        self.children.push(Anchored {
+            element,
+            h_attach,
+            v_attach,
+            offset,
+            size,
+        });
+    }
+
+    pub fn draw(&self, renderer: &Renderer) -> Result<()> {
+        let scale = self.scale_factor()?;
+        for child in &self.children {
+            let rect = self.resolve_rect(child, scale);
+            child.element.draw(renderer, &rect);
+        }
+        Ok(())
+    }
+
+    fn scale_factor(&self) -> Result<f32> {
+        match self.scale_mode {
+            ScaleMode::Unscaled(factor) => Ok(factor),
+            ScaleMode::Scaled => {
+                let canvas = browser::context()?
+                    .canvas()
+                    .ok_or_else(|| anyhow!("Container: canvas not found on context"))?;
+
+                let scale_x = canvas.width() as f32 / self.design_resolution.0 as f32;
+                let scale_y = canvas.height() as f32 / self.design_resolution.1 as f32;
+                Ok(scale_x.min(scale_y))
+            }
+        }
+    }
+
+    /// アンカー・オフセット・スケール係数を合成して要素の最終的な Rect を求める
+    fn resolve_rect(&self, child: &Anchored, scale: f32) -> Rect {
+        let width = (child.size.0 as f32 * scale) as i16;
+        let height = (child.size.1 as f32 * scale) as i16;
+        let design_width = (self.design_resolution.0 as f32 * scale) as i16;
+        let design_height = (self.design_resolution.1 as f32 * scale) as i16;
+
+        let x = match child.h_attach {
+            HAttach::Left => 0,
+            HAttach::Center => design_width / 2 - width / 2,
+            HAttach::Right => design_width - width,
+        };
+        let y = match child.v_attach {
+            VAttach::Top => 0,
+            VAttach::Middle => design_height / 2 - height / 2,
+            VAttach::Bottom => design_height - height,
+        };
+
+        Rect::new_from_x_y(
+            x + (child.offset.x as f32 * scale) as i16,
+            y + (child.offset.y as f32 * scale) as i16,
+            width,
+            height,
+        )
+    }
+}